@@ -36,18 +36,20 @@ pub async fn run(cli: Cli) -> anyhow::Result<Vec<RetrievedDependency>> {
 
     // 3. create collectors
     tracing::debug!("Building collectors");
-    let npm_retriever = licensebat_js::retriever::Npm::new(client.clone());
+    let npm_retriever = licensebat_js::retriever::Npm::with_store(client.clone(), store.clone());
     let npm_collector = licensebat_js::collector::Npm::new(npm_retriever.clone());
     let yarn_collector = licensebat_js::collector::Yarn::new(npm_retriever);
     let rust_collector = licensebat_rust::collector::Rust::with_crates_io_retriever(client.clone());
     let dart_collector =
         licensebat_dart::collector::Dart::with_hosted_retriever(client.clone(), store.clone());
+    let reuse_collector = licensebat_reuse::collector::Reuse::new();
 
     let file_collectors: Vec<Box<dyn FileCollector>> = vec![
         Box::new(npm_collector),
         Box::new(yarn_collector),
         Box::new(rust_collector),
         Box::new(dart_collector),
+        Box::new(reuse_collector),
     ];
 
     // 4. get dependency stream
@@ -60,7 +62,34 @@ pub async fn run(cli: Cli) -> anyhow::Result<Vec<RetrievedDependency>> {
     // 5. validate the dependencies according to the .licrc config
     tracing::debug!("Validating dependencies");
     let mut validated_deps = vec![];
-    while let Some(mut dependency) = stream.next().await {
+    while let Some(result) = stream.next().await {
+        let mut dependency = match result {
+            Ok(dependency) => dependency,
+            Err(e) if cli.fail_on_error => return Err(e.into()),
+            Err(e) => {
+                tracing::warn!(error = %e, "Couldn't retrieve a dependency");
+                let (name, version) = e.dependency();
+                validated_deps.push(RetrievedDependency {
+                    name: name.to_owned(),
+                    version: version.to_owned(),
+                    url: None,
+                    dependency_type: String::new(),
+                    validated: false,
+                    is_valid: false,
+                    is_ignored: false,
+                    error: Some(e.to_string()),
+                    licenses: None,
+                    clarified: false,
+                    comment: None,
+                });
+                continue;
+            }
+        };
+        // apply any matching [[clarify]] override before validating, so a
+        // pinned license takes precedence over whatever was auto-detected.
+        // No collector currently surfaces a license-file hash alongside its
+        // result, so `license_file_hash`-guarded entries never match yet.
+        licrc.apply_clarification(&mut dependency, None);
         // do the validation here
         licrc.validate(&mut dependency);
         validated_deps.push(dependency);
@@ -0,0 +1,19 @@
+pub mod check;
+
+/// Command line arguments accepted by the `licensebat` binary.
+#[derive(Debug, Clone, clap::Parser)]
+#[command(author, version, about)]
+pub struct Cli {
+    /// Path to the dependency file to check (e.g. `package-lock.json`).
+    pub dependency_file: String,
+
+    /// Path to the `.licrc` configuration file.
+    #[arg(long, default_value = ".licrc")]
+    pub licrc_file: String,
+
+    /// Fail the run as soon as a dependency can't be retrieved (a
+    /// transport/registry error), instead of reporting it as invalid and
+    /// continuing with the rest of the dependencies.
+    #[arg(long, default_value_t = false)]
+    pub fail_on_error: bool,
+}
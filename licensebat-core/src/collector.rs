@@ -0,0 +1,52 @@
+use crate::{RetrievalError, RetrievedDependency};
+use futures::{future::BoxFuture, stream::FuturesUnordered};
+
+/// Stream of per-dependency outcomes produced by a [`FileCollector`].
+///
+/// Every collector in the workspace — regardless of ecosystem, and
+/// regardless of whether its own retriever has an ecosystem-specific error
+/// type — has to agree on this exact item type, since [`FileCollector`] is
+/// used as a trait object (`Vec<Box<dyn FileCollector>>`) in the CLI. A
+/// retriever's own error is converted into a [`RetrievalError`] before it
+/// reaches this stream; a collector whose dependencies can't fail (e.g.
+/// `Reuse`, which reads already-declared SPDX metadata) simply never
+/// produces the `Err` variant.
+pub type RetrievedDependencyStream<'a> = FuturesUnordered<BoxFuture<'a, Result<RetrievedDependency, RetrievalError>>>;
+
+/// Result of building a [`RetrievedDependencyStream`] from a dependency
+/// file's contents, which can fail if the content isn't parseable.
+pub type RetrievedDependencyStreamResult<'a> = Result<RetrievedDependencyStream<'a>, CollectorError>;
+
+/// Errors that can happen while parsing a dependency file into the
+/// dependencies it declares, before any of them have been retrieved.
+#[derive(Debug, thiserror::Error)]
+pub enum CollectorError {
+    #[error("Error parsing dependency file: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("Error parsing SPDX document: {0}")]
+    Spdx(#[from] spdx_rs::error::SpdxError),
+}
+
+/// A source of dependencies for a given ecosystem (npm, cargo, pub, ...),
+/// identified by name.
+pub trait Collector: Send + Sync + std::fmt::Debug {
+    /// The name of the ecosystem this collector handles, e.g. `"npm"`.
+    fn get_name(&self) -> String;
+}
+
+/// A [`Collector`] that knows how to turn the contents of a specific
+/// dependency file (e.g. `package-lock.json`) into a [`RetrievedDependencyStream`].
+pub trait FileCollector: Collector {
+    /// The name of the dependency file this collector knows how to parse,
+    /// e.g. `"package-lock.json"`.
+    fn get_dependency_filename(&self) -> String;
+
+    /// Parses `dependency_file_content` and kicks off retrieval for every
+    /// dependency it declares.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CollectorError`] if `dependency_file_content` can't be
+    /// parsed as this collector's dependency file format.
+    fn get_dependencies(&self, dependency_file_content: &str) -> RetrievedDependencyStreamResult<'_>;
+}
@@ -0,0 +1,22 @@
+/// A human-readable note attached to a [`RetrievedDependency`](crate::RetrievedDependency),
+/// surfaced in reports so a reviewer understands why a dependency looks the
+/// way it does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    pub text: String,
+    /// Whether this comment can be dismissed once a reviewer has acted on it,
+    /// as opposed to a permanent note about the dependency.
+    pub removable: bool,
+}
+
+impl Comment {
+    /// Creates a [`Comment`] that a reviewer can dismiss once they've acted on
+    /// it.
+    #[must_use]
+    pub fn removable(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            removable: true,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+/// A dependency declared by a project's dependency file, before any license
+/// information has been retrieved for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub name: String,
+    pub version: String,
+}
@@ -0,0 +1,16 @@
+pub mod collector;
+pub mod licrc;
+mod comment;
+mod dependency;
+mod retrieval_error;
+mod retriever;
+mod retrieved_dependency;
+mod spdx_expression;
+
+pub use collector::{Collector, FileCollector};
+pub use comment::Comment;
+pub use dependency::Dependency;
+pub use retrieval_error::RetrievalError;
+pub use retriever::Retriever;
+pub use retrieved_dependency::RetrievedDependency;
+pub use spdx_expression::{SpdxExpression, SpdxExpressionError};
@@ -0,0 +1,228 @@
+use crate::{Comment, RetrievedDependency, SpdxExpression};
+use semver::{Version, VersionReq};
+use std::path::Path;
+
+/// A project's `.licrc` configuration: which licenses are acceptable, which
+/// dependencies to ignore, and per-dependency overrides for the cases those
+/// lists can't express.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct LicRc {
+    /// License identifiers a dependency is allowed to declare.
+    #[serde(default)]
+    pub accepted: Vec<String>,
+    /// License identifiers that immediately fail validation, even if they'd
+    /// otherwise satisfy `accepted` (e.g. a copyleft license the project
+    /// can't use despite being "free").
+    #[serde(default)]
+    pub denied: Vec<String>,
+    /// Dependency names to skip validation for entirely.
+    #[serde(default)]
+    pub ignored: Vec<String>,
+    /// Per-dependency overrides for licenses a retriever couldn't
+    /// automatically determine (or determined incorrectly).
+    #[serde(default)]
+    pub clarify: Vec<Clarification>,
+}
+
+/// An override for a specific dependency's license, for the cases a
+/// retriever can't resolve on its own: an unpublished/mislabeled license, a
+/// vendored fork, or a registry that simply doesn't expose the field.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Clarification {
+    /// Name of the dependency this override applies to.
+    pub name: String,
+    /// Restricts the override to versions matching this requirement (e.g.
+    /// `"^1.2"`). Applies to every version when omitted.
+    #[serde(default)]
+    pub version: Option<VersionReq>,
+    /// The SPDX expression to use instead of whatever was retrieved.
+    pub license: String,
+    /// Restricts the override to a dependency whose license file hashes to
+    /// this value, as a safeguard against the override silently going stale
+    /// once a later release actually changes its license. When present, a
+    /// caller that can't supply the dependency's actual license-file hash
+    /// (see [`Clarification::matches`]) never matches this entry, rather
+    /// than applying it unchecked.
+    #[serde(default)]
+    pub license_file_hash: Option<String>,
+}
+
+impl Clarification {
+    /// Whether this override applies to `name`/`version`/`license_file_hash`.
+    ///
+    /// `license_file_hash` is matched against the hash of the dependency's
+    /// actual license file, as supplied by the caller (`LicRc` itself has no
+    /// access to that file). An entry that specifies a hash fails closed: it
+    /// never matches unless the caller can supply one that agrees with it.
+    fn matches(&self, name: &str, version: &str, license_file_hash: Option<&str>) -> bool {
+        if self.name != name {
+            return false;
+        }
+        match (&self.version, Version::parse(version)) {
+            (Some(req), Ok(version)) if !req.matches(&version) => return false,
+            (Some(_), Err(_)) => return false,
+            _ => {}
+        }
+        match &self.license_file_hash {
+            None => true,
+            Some(expected) => license_file_hash.is_some_and(|actual| actual == expected),
+        }
+    }
+}
+
+impl LicRc {
+    /// Reads and parses a `.licrc` file from `path`, relative to the current
+    /// directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` can't be read, or doesn't contain a valid
+    /// `.licrc`.
+    pub fn from_relative_path(path: impl AsRef<Path>) -> Result<Self, LicRcError> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    /// Applies the first matching `[[clarify]]` entry to `dependency`,
+    /// overriding whatever licenses were retrieved for it. A dependency
+    /// that doesn't match any entry is left untouched.
+    ///
+    /// `license_file_hash` should be the hash of the dependency's actual
+    /// license file, when the caller has one available; an entry that
+    /// requires a hash never matches without it.
+    pub fn apply_clarification(&self, dependency: &mut RetrievedDependency, license_file_hash: Option<&str>) {
+        let Some(clarification) = self
+            .clarify
+            .iter()
+            .find(|c| c.matches(&dependency.name, &dependency.version, license_file_hash))
+        else {
+            return;
+        };
+
+        dependency.licenses = Some(vec![clarification.license.clone()]);
+        dependency.error = None;
+        dependency.clarified = true;
+        dependency.comment = Some(Comment::removable(format!(
+            "License overridden by a `[[clarify]]` entry in `.licrc`: {}",
+            clarification.license
+        )));
+    }
+
+    /// Validates `dependency`'s licenses against `accepted`/`denied`/`ignored`,
+    /// setting [`RetrievedDependency::validated`] and
+    /// [`RetrievedDependency::is_valid`] accordingly.
+    pub fn validate(&self, dependency: &mut RetrievedDependency) {
+        dependency.validated = true;
+
+        if self.ignored.iter().any(|name| name == &dependency.name) {
+            dependency.is_ignored = true;
+            dependency.is_valid = true;
+            return;
+        }
+
+        let Some(licenses) = dependency.licenses.as_ref() else {
+            dependency.is_valid = false;
+            return;
+        };
+
+        // track the first offending identifier, and the list it failed
+        // against, so a failure can point at it instead of just flipping
+        // `is_valid`
+        let mut unmet_requirement = None;
+
+        dependency.is_valid = licenses.iter().all(|license| {
+            let Ok(expression) = SpdxExpression::parse(license) else {
+                // couldn't be parsed as an SPDX expression earlier on; fall
+                // back to a plain string comparison against the raw lists
+                if self.denied.iter().any(|d| d == license) {
+                    unmet_requirement.get_or_insert_with(|| (license.clone(), "is in the `.licrc` denied list"));
+                    return false;
+                }
+                let ok = self.accepted.iter().any(|a| a == license);
+                if !ok {
+                    unmet_requirement.get_or_insert_with(|| (license.clone(), "isn't in the `.licrc` accepted list"));
+                }
+                return ok;
+            };
+
+            if expression.requires_any(&self.denied) {
+                let offending = self
+                    .denied
+                    .iter()
+                    .find(|d| expression.requires_any(std::slice::from_ref(*d)))
+                    .cloned()
+                    .unwrap_or_else(|| expression.to_string());
+                unmet_requirement.get_or_insert((offending, "is in the `.licrc` denied list"));
+                return false;
+            }
+            let ok = expression.is_satisfied_by(&self.accepted);
+            if !ok {
+                let offending = expression.first_unmet_requirement(&self.accepted).unwrap_or_else(|| expression.to_string());
+                unmet_requirement.get_or_insert((offending, "isn't in the `.licrc` accepted list"));
+            }
+            ok
+        });
+
+        if let Some((offending, reason)) = unmet_requirement {
+            dependency.comment = Some(Comment::removable(format!(
+                "License validation failed on `{offending}` — it {reason}."
+            )));
+        }
+    }
+}
+
+/// Errors that can happen while reading or parsing a `.licrc` file.
+#[derive(Debug, thiserror::Error)]
+pub enum LicRcError {
+    #[error("Error reading .licrc file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Error parsing .licrc file: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Clarification;
+
+    fn clarification(version: Option<&str>, hash: Option<&str>) -> Clarification {
+        Clarification {
+            name: "left-pad".to_owned(),
+            version: version.map(|req| req.parse().unwrap()),
+            license: "MIT".to_owned(),
+            license_file_hash: hash.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn matches_name_regardless_of_version_when_unconstrained() {
+        let clarification = clarification(None, None);
+        assert!(clarification.matches("left-pad", "1.2.3", None));
+        assert!(!clarification.matches("right-pad", "1.2.3", None));
+    }
+
+    #[test]
+    fn matches_only_versions_satisfying_the_requirement() {
+        let clarification = clarification(Some("^1.2"), None);
+        assert!(clarification.matches("left-pad", "1.2.3", None));
+        assert!(!clarification.matches("left-pad", "2.0.0", None));
+    }
+
+    #[test]
+    fn unparseable_version_never_matches_a_version_constrained_entry() {
+        let clarification = clarification(Some("^1.2"), None);
+        assert!(!clarification.matches("left-pad", "not-a-semver", None));
+    }
+
+    #[test]
+    fn hash_guarded_entry_only_matches_the_expected_hash() {
+        let clarification = clarification(None, Some("deadbeef"));
+        assert!(clarification.matches("left-pad", "1.2.3", Some("deadbeef")));
+        assert!(!clarification.matches("left-pad", "1.2.3", Some("stale")));
+    }
+
+    #[test]
+    fn hash_guarded_entry_fails_closed_when_caller_has_no_hash() {
+        let clarification = clarification(None, Some("deadbeef"));
+        assert!(!clarification.matches("left-pad", "1.2.3", None));
+    }
+}
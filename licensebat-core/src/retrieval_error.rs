@@ -0,0 +1,43 @@
+/// A dependency's retrieval failed outright — as opposed to succeeding with
+/// `licenses: None`, which is a legitimate (if unfortunate) outcome rather
+/// than a transport failure.
+///
+/// Every [`Retriever`](crate::Retriever) implementation has its own
+/// ecosystem-specific error type (rate limits, malformed tarballs, whatever
+/// is particular to that registry), but [`FileCollector`](crate::FileCollector)
+/// is used as a trait object, so every collector's stream has to settle on
+/// one concrete error type. This is that type: it keeps just enough
+/// structure — which dependency failed, and why — for callers like the CLI
+/// to still report it instead of silently dropping it, while the original
+/// ecosystem-specific error is preserved as [`source`](std::error::Error::source).
+#[derive(Debug, thiserror::Error)]
+#[error("couldn't retrieve '{name}@{version}': {source}")]
+pub struct RetrievalError {
+    name: String,
+    version: String,
+    #[source]
+    source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl RetrievalError {
+    /// Wraps an ecosystem-specific retrieval error with the name/version of
+    /// the dependency it happened for.
+    pub fn new(
+        name: impl Into<String>,
+        version: impl Into<String>,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            source: source.into(),
+        }
+    }
+
+    /// The name/version of the dependency this error happened for, so
+    /// callers can still report it rather than dropping it from the output.
+    #[must_use]
+    pub fn dependency(&self) -> (&str, &str) {
+        (&self.name, &self.version)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::Comment;
+
+/// A dependency along with whatever license information a collector was able
+/// to retrieve for it, and the outcome of validating that information against
+/// a `.licrc`.
+#[derive(Debug, Clone)]
+pub struct RetrievedDependency {
+    pub name: String,
+    pub version: String,
+    pub url: Option<String>,
+    pub dependency_type: String,
+    /// Whether this dependency's licenses have already been validated: by
+    /// [`LicRc::validate`](crate::licrc::LicRc::validate), or at collection
+    /// time by a collector that reads licenses the project declares itself
+    /// (e.g. the REUSE collector).
+    pub validated: bool,
+    pub is_valid: bool,
+    pub is_ignored: bool,
+    pub error: Option<String>,
+    pub licenses: Option<Vec<String>>,
+    pub comment: Option<Comment>,
+    /// Whether a `.licrc` `[[clarify]]` entry overrode this dependency's
+    /// licenses, as opposed to them coming straight from the retriever.
+    pub clarified: bool,
+}
@@ -0,0 +1,14 @@
+use crate::{RetrievalError, RetrievedDependency};
+use futures::Future;
+
+/// Trait used by a [`crate::FileCollector`] to retrieve a single dependency's
+/// license information from its original source (a registry, a hosted
+/// package index, ...).
+pub trait Retriever: Send + Sync {
+    /// Future that resolves to a [`RetrievedDependency`], or a
+    /// [`RetrievalError`] when the dependency couldn't be retrieved at all.
+    type Response: Future<Output = Result<RetrievedDependency, RetrievalError>> + Send;
+
+    /// Retrieves a dependency's information from the original source.
+    fn get_dependency(&self, dep_name: &str, dep_version: &str) -> Self::Response;
+}
@@ -0,0 +1,116 @@
+use spdx::Expression;
+
+/// A parsed SPDX license expression, e.g. `"(MIT OR Apache-2.0) AND Unicode-DFS-2016"`.
+///
+/// Wrapping [`spdx::Expression`] lets a `.licrc`'s `accepted`/`denied` lists be
+/// evaluated as the boolean expression they represent instead of compared
+/// against the raw license string, which breaks as soon as a dependency
+/// declares more than one license.
+#[derive(Debug, Clone)]
+pub struct SpdxExpression(Expression);
+
+/// Errors that can happen while parsing a license string as an SPDX expression.
+#[derive(Debug, thiserror::Error)]
+pub enum SpdxExpressionError {
+    #[error("'{0}' is not a valid SPDX license expression: {1}")]
+    Parse(String, spdx::ParseError),
+}
+
+impl SpdxExpression {
+    /// Parses a raw license string retrieved from a registry into an SPDX
+    /// [`Expression`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpdxExpressionError::Parse`] if `raw` isn't a valid SPDX
+    /// license expression.
+    pub fn parse(raw: &str) -> Result<Self, SpdxExpressionError> {
+        Expression::parse(raw)
+            .map(Self)
+            .map_err(|source| SpdxExpressionError::Parse(raw.to_owned(), source))
+    }
+
+    /// Returns `true` if this expression can be satisfied using only the
+    /// license identifiers in `allowed`.
+    ///
+    /// An `OR` node passes when *any* branch is allowed; an `AND` node passes
+    /// only when *all* of its branches are allowed.
+    #[must_use]
+    pub fn is_satisfied_by(&self, allowed: &[String]) -> bool {
+        self.0
+            .evaluate(|req| req.license.id().is_some_and(|id| allowed.iter().any(|a| a == id.name)))
+    }
+
+    /// Returns the first license identifier in this expression that isn't
+    /// present in `allowed`, so callers can point the user at the specific
+    /// offending identifier rather than the whole expression. Returns `None`
+    /// when the expression is already satisfied by `allowed`.
+    #[must_use]
+    pub fn first_unmet_requirement(&self, allowed: &[String]) -> Option<String> {
+        if self.is_satisfied_by(allowed) {
+            return None;
+        }
+        self.0.requirements().find_map(|expr_req| {
+            let id = expr_req.req.license.id()?;
+            (!allowed.iter().any(|a| a == id.name)).then(|| id.name.to_owned())
+        })
+    }
+
+    /// Returns `true` if any license identifier this expression requires is
+    /// present in `denied`, regardless of where it sits in the expression's
+    /// `AND`/`OR` structure — unlike [`Self::is_satisfied_by`], a single
+    /// denied branch is enough, since a dependency shouldn't be usable under
+    /// a denied license just because an alternative is also offered.
+    #[must_use]
+    pub fn requires_any(&self, denied: &[String]) -> bool {
+        self.0
+            .requirements()
+            .filter_map(|expr_req| expr_req.req.license.id())
+            .any(|id| denied.iter().any(|d| d == id.name))
+    }
+}
+
+impl std::fmt::Display for SpdxExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpdxExpression;
+
+    fn allowed(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| (*id).to_owned()).collect()
+    }
+
+    #[test]
+    fn is_satisfied_by_passes_an_or_with_any_branch_allowed() {
+        let expression = SpdxExpression::parse("MIT OR Apache-2.0").unwrap();
+        assert!(expression.is_satisfied_by(&allowed(&["MIT"])));
+    }
+
+    #[test]
+    fn is_satisfied_by_requires_every_branch_of_an_and() {
+        let expression = SpdxExpression::parse("MIT AND Apache-2.0").unwrap();
+        assert!(!expression.is_satisfied_by(&allowed(&["MIT"])));
+        assert!(expression.is_satisfied_by(&allowed(&["MIT", "Apache-2.0"])));
+    }
+
+    #[test]
+    fn first_unmet_requirement_names_the_offending_identifier() {
+        let expression = SpdxExpression::parse("(MIT OR ISC) AND GPL-3.0-only").unwrap();
+        assert_eq!(
+            expression.first_unmet_requirement(&allowed(&["MIT", "ISC"])),
+            Some("GPL-3.0-only".to_owned())
+        );
+        assert_eq!(expression.first_unmet_requirement(&allowed(&["MIT", "GPL-3.0-only"])), None);
+    }
+
+    #[test]
+    fn requires_any_catches_a_denied_branch_inside_an_or() {
+        let expression = SpdxExpression::parse("MIT OR GPL-3.0-only").unwrap();
+        assert!(expression.requires_any(&allowed(&["GPL-3.0-only"])));
+        assert!(!expression.requires_any(&allowed(&["AGPL-3.0-only"])));
+    }
+}
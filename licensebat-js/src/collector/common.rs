@@ -11,12 +11,10 @@ where
     I: Iterator<Item = Dependency>,
     R: Retriever + 'a,
 {
+    // `get_dependency` now reports genuine infrastructure failures as `Err`
+    // instead of folding them into a fake dependency, so the stream carries
+    // that result through instead of unwrapping (and panicking on) it.
     deps.into_iter()
-        .map(|dep| {
-            retriever
-                .get_dependency(&dep.name, &dep.version)
-                .map(std::result::Result::unwrap) // TODO: this will never be not ok! so if'ts ok. consider removing the need of using this as a result.
-                .boxed()
-        })
+        .map(|dep| retriever.get_dependency(&dep.name, &dep.version).boxed())
         .collect()
 }
@@ -0,0 +1,74 @@
+/// Normalizes a set of raw license identifiers collected from registry
+/// metadata.
+///
+/// npm's `license`/`licenses` fields are notoriously inconsistent: legacy
+/// slash-separated values (`"MIT/Apache-2.0"`), duplicated entries, and
+/// arbitrary ordering. For an entry that's just a flat list of alternatives
+/// (no parentheses, no `AND`), this splits it on both `/` and the literal
+/// `" OR "`, trims whitespace, deduplicates and sorts the pieces
+/// deterministically, so the result only depends on the set of declared
+/// identifiers rather than how the package author happened to write them —
+/// matching how `cargo-license` normalizes the equivalent Cargo.toml field.
+///
+/// An entry that already contains `(` or `" AND "` is a compound SPDX
+/// expression (e.g. `"(MIT OR Apache-2.0) AND Unicode-DFS-2016"`) whose
+/// grouping and operator precedence would be destroyed by naively splitting
+/// on `/`/`OR`, so it's only trimmed and passed through unchanged. Collectors
+/// join the result with `" OR "` before handing it to the SPDX parser.
+#[must_use]
+pub fn normalize_licenses(raw: &[String]) -> Vec<String> {
+    let mut identifiers: Vec<String> = raw
+        .iter()
+        .flat_map(|license| {
+            let license = license.trim();
+            if license.contains('(') || license.contains(" AND ") {
+                vec![license]
+            } else {
+                license.split('/').flat_map(|part| part.split(" OR ")).collect()
+            }
+        })
+        .map(str::trim)
+        .filter(|license| !license.is_empty())
+        .map(str::to_owned)
+        .collect();
+
+    identifiers.sort_unstable();
+    identifiers.dedup();
+    identifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_licenses;
+
+    fn owned(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| (*id).to_owned()).collect()
+    }
+
+    #[test]
+    fn splits_and_sorts_legacy_slash_separated_values() {
+        assert_eq!(normalize_licenses(&owned(&["Apache-2.0/MIT"])), owned(&["Apache-2.0", "MIT"]));
+    }
+
+    #[test]
+    fn splits_a_flat_or_list() {
+        assert_eq!(normalize_licenses(&owned(&["MIT OR ISC"])), owned(&["ISC", "MIT"]));
+    }
+
+    #[test]
+    fn dedups_across_entries() {
+        assert_eq!(normalize_licenses(&owned(&["MIT", "MIT/ISC"])), owned(&["ISC", "MIT"]));
+    }
+
+    #[test]
+    fn leaves_a_compound_expression_with_parens_untouched() {
+        let raw = owned(&["(MIT OR Apache-2.0) AND Unicode-DFS-2016"]);
+        assert_eq!(normalize_licenses(&raw), owned(&["(MIT OR Apache-2.0) AND Unicode-DFS-2016"]));
+    }
+
+    #[test]
+    fn leaves_an_and_expression_without_parens_untouched() {
+        let raw = owned(&["MIT AND Unicode-DFS-2016"]);
+        assert_eq!(normalize_licenses(&raw), owned(&["MIT AND Unicode-DFS-2016"]));
+    }
+}
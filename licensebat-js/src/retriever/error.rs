@@ -0,0 +1,43 @@
+/// Errors that can happen while retrieving a dependency's metadata from the
+/// registry.
+///
+/// These represent genuine infrastructure failures. A dependency whose
+/// metadata simply doesn't declare a license is *not* one of these — it still
+/// comes back as `Ok` with `licenses: None`, since that's a legitimate (if
+/// unfortunate) outcome rather than a transport failure.
+#[derive(Debug, thiserror::Error)]
+pub enum RetrieverError {
+    /// The registry has no package by this name (it responded with a 404).
+    #[error("'{name}@{version}' wasn't found on the registry")]
+    NotFound { name: String, version: String },
+    /// The request failed after exhausting retries, or failed in a way that
+    /// isn't worth retrying (e.g. malformed JSON).
+    #[error("network error while fetching '{name}@{version}': {source}")]
+    Network {
+        name: String,
+        version: String,
+        #[source]
+        source: reqwest::Error,
+    },
+}
+
+impl RetrieverError {
+    /// The name/version of the dependency this error happened for, so
+    /// callers can still report it rather than dropping it from the output.
+    #[must_use]
+    pub fn dependency(&self) -> (&str, &str) {
+        match self {
+            Self::NotFound { name, version } | Self::Network { name, version, .. } => {
+                (name, version)
+            }
+        }
+    }
+}
+
+impl From<RetrieverError> for licensebat_core::RetrievalError {
+    fn from(error: RetrieverError) -> Self {
+        let (name, version) = error.dependency();
+        let (name, version) = (name.to_owned(), version.to_owned());
+        Self::new(name, version, error)
+    }
+}
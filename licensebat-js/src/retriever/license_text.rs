@@ -0,0 +1,72 @@
+use flate2::read::GzDecoder;
+use reqwest::Client;
+use tar::Archive;
+
+/// Below this askalono confidence score a text match is considered too
+/// unreliable to use and the dependency falls back to `NO-LICENSE`.
+pub const DEFAULT_CONFIDENCE_THRESHOLD: f32 = 0.8;
+
+/// Name prefixes considered when looking for a license file inside a
+/// downloaded tarball, in order of how likely they are to actually contain
+/// license text.
+const CANDIDATE_PREFIXES: [&str; 3] = ["license", "copying", "readme"];
+
+/// A license identified by matching the text of a file against askalono's
+/// corpus, along with the confidence score of the match.
+#[derive(Debug, Clone)]
+pub struct TextMatch {
+    pub license: String,
+    pub score: f32,
+}
+
+/// Downloads the package tarball at `tarball_url`, looks for the file that
+/// best resembles a license notice (`LICENSE*`, `COPYING*`, `README*`) and
+/// identifies its license via askalono text matching.
+///
+/// Returns `None` if the tarball can't be downloaded/extracted, no candidate
+/// file is found, or no match clears `threshold`.
+pub async fn detect_license_from_tarball(
+    client: &Client,
+    tarball_url: &str,
+    store: &askalono::Store,
+    threshold: f32,
+) -> Option<TextMatch> {
+    let tarball = client.get(tarball_url).send().await.ok()?.bytes().await.ok()?;
+
+    best_candidate_match(&tarball, store)
+        .filter(|text_match| text_match.score >= threshold)
+}
+
+fn best_candidate_match(tarball: &[u8], store: &askalono::Store) -> Option<TextMatch> {
+    let mut archive = Archive::new(GzDecoder::new(tarball));
+
+    archive
+        .entries()
+        .ok()?
+        .filter_map(std::result::Result::ok)
+        .filter_map(|mut entry| {
+            let is_candidate = entry
+                .path()
+                .ok()
+                .and_then(|path| path.to_str().map(is_candidate_license_file))
+                .unwrap_or(false);
+            if !is_candidate {
+                return None;
+            }
+            let mut contents = String::new();
+            std::io::Read::read_to_string(&mut entry, &mut contents).ok()?;
+            let matched = store.analyze(&contents.into());
+            Some(TextMatch {
+                license: matched.name.to_owned(),
+                score: matched.score,
+            })
+        })
+        .max_by(|a, b| a.score.total_cmp(&b.score))
+}
+
+fn is_candidate_license_file(path: &str) -> bool {
+    path.rsplit('/')
+        .next()
+        .map(str::to_lowercase)
+        .is_some_and(|name| CANDIDATE_PREFIXES.iter().any(|prefix| name.starts_with(prefix)))
+}
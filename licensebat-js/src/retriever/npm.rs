@@ -1,25 +1,41 @@
-use crate::retriever::npm_metadata::NpmMetadata;
-use futures::{
-    future::{self, BoxFuture},
-    Future, FutureExt, TryFutureExt,
+use crate::retriever::{
+    error::RetrieverError,
+    license_text::{self, DEFAULT_CONFIDENCE_THRESHOLD},
+    npm_metadata::NpmMetadata,
 };
-use licensebat_core::{Comment, Dependency, RetrievedDependency};
+use futures::{future::BoxFuture, FutureExt};
+use licensebat_core::{Comment, Dependency, Retriever, RetrievedDependency, SpdxExpression};
 use reqwest::Client;
 use serde_json::Value;
+use std::sync::Arc;
 use tracing::instrument;
 
-/// Trait used by the [`Npm`] struct to retrieve dependencies.
-pub trait Retriever: Send + Sync + std::fmt::Debug {
-    /// Future that resolves to a [`RetrievedDependency`].
-    /// It cannot fail.
-    type Response: Future<Output = RetrievedDependency> + Send;
-    /// Validates dependency's information from the original source.
-    fn get_dependency(&self, dep_name: &str, dep_version: &str) -> Self::Response;
-}
+/// Number of times a transient (network/5xx) failure is retried before
+/// giving up on a dependency.
+const MAX_RETRIES: u32 = 3;
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Npm {
     client: Client,
+    /// askalono corpus used to identify a license by text matching when the
+    /// registry metadata doesn't declare one. `None` when no store could be
+    /// loaded, in which case the text-matching fallback is skipped.
+    store: Arc<Option<askalono::Store>>,
+    /// Minimum askalono confidence score (0.0-1.0) a text match needs to
+    /// clear before it's trusted; see [`license_text::DEFAULT_CONFIDENCE_THRESHOLD`].
+    confidence_threshold: f32,
+}
+
+impl std::fmt::Debug for Npm {
+    // `askalono::Store` doesn't implement `Debug`, so `store` is reported by
+    // presence rather than derived.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Npm")
+            .field("client", &self.client)
+            .field("store", &self.store.is_some())
+            .field("confidence_threshold", &self.confidence_threshold)
+            .finish()
+    }
 }
 
 impl Default for Npm {
@@ -33,16 +49,50 @@ impl Default for Npm {
 impl Npm {
     /// Creates a new [`Retriever`] using the given [`reqwest::Client`].
     #[must_use]
-    pub const fn new(client: Client) -> Self {
-        Self { client }
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            store: Arc::new(None),
+            confidence_threshold: DEFAULT_CONFIDENCE_THRESHOLD,
+        }
+    }
+
+    /// Creates a new [`Retriever`] that falls back to askalono text matching
+    /// (using `store`) when a dependency's registry metadata doesn't declare
+    /// a license, mirroring the Dart collector's `with_hosted_retriever`. Text
+    /// matches are trusted above [`license_text::DEFAULT_CONFIDENCE_THRESHOLD`];
+    /// use [`Self::with_confidence_threshold`] to override it.
+    #[must_use]
+    pub fn with_store(client: Client, store: Arc<Option<askalono::Store>>) -> Self {
+        Self {
+            client,
+            store,
+            confidence_threshold: DEFAULT_CONFIDENCE_THRESHOLD,
+        }
+    }
+
+    /// Overrides the minimum askalono confidence score a text match needs to
+    /// clear before it's trusted, instead of [`license_text::DEFAULT_CONFIDENCE_THRESHOLD`].
+    #[must_use]
+    pub const fn with_confidence_threshold(mut self, confidence_threshold: f32) -> Self {
+        self.confidence_threshold = confidence_threshold;
+        self
     }
 }
 
 impl Retriever for Npm {
-    type Response = BoxFuture<'static, RetrievedDependency>;
+    type Response = BoxFuture<'static, Result<RetrievedDependency, licensebat_core::RetrievalError>>;
 
     /// Gets a dependency from NPM.
-    /// This method attacks the npm api.
+    /// This method attacks the npm api. When the registry metadata doesn't
+    /// declare a license, it falls back to downloading the package tarball
+    /// and identifying its license via askalono text matching.
+    ///
+    /// Transient failures (timeouts, connection resets, 5xx responses) are
+    /// retried with an exponential backoff before giving up and returning a
+    /// [`RetrieverError`] (converted into a [`licensebat_core::RetrievalError`]
+    /// for the shared stream type); a 404 is reported immediately since
+    /// retrying won't help.
     #[instrument(skip(self), level = "debug")]
     fn get_dependency(&self, dep_name: &str, dep_version: &str) -> Self::Response {
         let url = format!("https://registry.npmjs.org/{}", dep_name);
@@ -51,48 +101,152 @@ impl Retriever for Npm {
             name: dep_name.to_string(),
             version: dep_version.to_string(),
         };
-        let dep_clone = dependency.clone();
         let dependency_version = dep_version.to_string();
+        let client = self.client.clone();
+        let store = self.store.clone();
+        let confidence_threshold = self.confidence_threshold;
 
-        self.client
-            .get(&url)
-            .send()
-            .and_then(reqwest::Response::json)
-            .map_ok(|metadata: Value| {
-                // get general license
-                let license = metadata["license"].clone();
-                // get info from specific version
-                let version = metadata["versions"][dependency_version].clone();
-                serde_json::from_value::<NpmMetadata>(version)
-                    .ok()
-                    .and_then(|mut md| {
-                        if md.license.is_none() {
-                            // use generic if no license is found in the version
-                            md.license = match license {
-                                Value::String(lic) => Some(lic),
-                                Value::Object(lic) => lic
-                                    .get("type")
-                                    .and_then(serde_json::Value::as_str)
-                                    .map(std::borrow::ToOwned::to_owned),
-                                _ => None,
-                            }
-                        }
-                        md.get_licenses()
-                    })
-            })
-            .map_ok(move |licenses: Option<Vec<String>>| {
-                build_retrieved_dependency(&dep_clone, licenses, None)
-            })
-            .or_else(move |e| future::ok(build_retrieved_dependency(&dependency, None, Some(e))))
-            .map(std::result::Result::<RetrievedDependency, std::convert::Infallible>::unwrap)
-            .boxed()
+        async move {
+            let mut attempt = 0;
+            let result: Result<RetrievedDependency, RetrieverError> = loop {
+                match fetch_retrieved_dependency(
+                    &client,
+                    &url,
+                    &dependency_version,
+                    &dependency,
+                    store.as_ref(),
+                    confidence_threshold,
+                )
+                .await
+                {
+                    Ok(retrieved) => break Ok(retrieved),
+                    Err(source) if source.status() == Some(reqwest::StatusCode::NOT_FOUND) => {
+                        break Err(RetrieverError::NotFound {
+                            name: dependency.name.clone(),
+                            version: dependency.version.clone(),
+                        })
+                    }
+                    Err(source) if attempt < MAX_RETRIES && is_transient(&source) => {
+                        attempt += 1;
+                        tokio::time::sleep(std::time::Duration::from_millis(200 * 2u64.pow(attempt))).await;
+                    }
+                    Err(source) => {
+                        break Err(RetrieverError::Network {
+                            name: dependency.name.clone(),
+                            version: dependency.version.clone(),
+                            source,
+                        })
+                    }
+                }
+            };
+            result.map_err(Into::into)
+        }
+        .boxed()
+    }
+}
+
+/// Whether a request failure is worth retrying, as opposed to a permanent
+/// rejection (4xx, malformed body) that would just fail the same way again.
+fn is_transient(error: &reqwest::Error) -> bool {
+    error.is_timeout()
+        || error.is_connect()
+        || error.status().is_some_and(|status| status.is_server_error())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_transient;
+
+    fn error_with_status(status: u16) -> reqwest::Error {
+        let response = http::Response::builder().status(status).body(Vec::new()).unwrap();
+        reqwest::Response::from(response)
+            .error_for_status()
+            .expect_err("a non-2xx status should produce an error")
+    }
+
+    #[test]
+    fn retries_server_errors() {
+        assert!(is_transient(&error_with_status(503)));
+    }
+
+    #[test]
+    fn does_not_retry_a_not_found() {
+        assert!(!is_transient(&error_with_status(404)));
+    }
+
+    #[test]
+    fn does_not_retry_other_client_errors() {
+        assert!(!is_transient(&error_with_status(403)));
+    }
+}
+
+async fn fetch_retrieved_dependency(
+    client: &Client,
+    url: &str,
+    dependency_version: &str,
+    dependency: &Dependency,
+    store: &Option<askalono::Store>,
+    confidence_threshold: f32,
+) -> Result<RetrievedDependency, reqwest::Error> {
+    let metadata: Value = client.get(url).send().await?.error_for_status()?.json().await?;
+    // get general license
+    let license = metadata["license"].clone();
+    // get info from specific version
+    let version = metadata["versions"][dependency_version].clone();
+    let mut md = serde_json::from_value::<NpmMetadata>(version).unwrap_or(NpmMetadata {
+        license: None,
+        licenses: None,
+        dist: None,
+    });
+    if md.license.is_none() {
+        // use generic if no license is found in the version
+        md.license = match license {
+            Value::String(lic) => Some(lic),
+            Value::Object(lic) => lic
+                .get("type")
+                .and_then(serde_json::Value::as_str)
+                .map(std::borrow::ToOwned::to_owned),
+            _ => None,
+        }
+    }
+    let licenses = md.get_licenses();
+    let tarball = md.dist.map(|dist| dist.tarball);
+
+    Ok(match (licenses, store, tarball) {
+        (Some(licenses), ..) => build_retrieved_dependency(dependency, Some(licenses)),
+        (None, Some(store), Some(tarball)) => {
+            let text_match =
+                license_text::detect_license_from_tarball(client, &tarball, store, confidence_threshold)
+                    .await;
+            build_retrieved_dependency_from_text_match(dependency, text_match)
+        }
+        (None, ..) => build_retrieved_dependency(dependency, None),
+    })
+}
+
+/// Builds a [`RetrievedDependency`] from an askalono text match on the
+/// package's tarball, annotating the result so reviewers know the license
+/// wasn't declared by the registry but detected by similarity.
+fn build_retrieved_dependency_from_text_match(
+    dependency: &Dependency,
+    text_match: Option<license_text::TextMatch>,
+) -> RetrievedDependency {
+    match text_match {
+        Some(license_text::TextMatch { license, score }) => {
+            let mut retrieved = build_retrieved_dependency(dependency, Some(vec![license.clone()]));
+            retrieved.comment = Some(Comment::removable(format!(
+                "No license was declared in the registry metadata. Detected **{license}** from the package's tarball via text matching ({:.0}% confidence) — please double-check it's accurate.",
+                score * 100.0
+            )));
+            retrieved
+        }
+        None => build_retrieved_dependency(dependency, None),
     }
 }
 
 fn build_retrieved_dependency(
     dependency: &Dependency,
     licenses: Option<Vec<String>>,
-    error: Option<reqwest::Error>,
 ) -> RetrievedDependency {
     let url = format!(
         "https://www.npmjs.com/package/{}/v/{}",
@@ -101,17 +255,36 @@ fn build_retrieved_dependency(
 
     let has_licenses = licenses.is_some();
 
+    // Licenses declared in an npm `licenses` array are alternatives (the
+    // dependency can be used under any one of them), so they're joined as an
+    // `OR` expression before being handed to the SPDX parser.
+    let raw_expression = licenses
+        .as_ref()
+        .filter(|licenses| !licenses.is_empty())
+        .map(|licenses| licenses.join(" OR "));
+    let expression = raw_expression.as_deref().map(SpdxExpression::parse);
+
+    let (licenses, spdx_comment) = match expression {
+        Some(Ok(expression)) => (Some(vec![expression.to_string()]), None),
+        Some(Err(_)) => (
+            licenses,
+            Some(Comment::removable(format!(
+                "Couldn't parse the license `{}` as a valid SPDX expression. Consider adding a `[[clarify]]` entry for this dependency.",
+                raw_expression.unwrap_or_default()
+            ))),
+        ),
+        None => (licenses, None),
+    };
+
     RetrievedDependency {
         name: dependency.name.clone(),
         version: dependency.version.clone(),
         url: Some(url),
         dependency_type: "npm".to_owned(),
         validated: false,
-        is_valid: has_licenses && error.is_none(),
+        is_valid: has_licenses,
         is_ignored: false,
-        error: if let Some(err) = error {
-            Some(err.to_string())
-        } else if has_licenses {
+        error: if has_licenses {
             None
         } else {
             Some("No License".to_owned())
@@ -121,8 +294,9 @@ fn build_retrieved_dependency(
         } else {
             Some(vec!["NO-LICENSE".to_string()])
         },
+        clarified: false,
         comment: if has_licenses {
-            None
+            spdx_comment
         } else {
             Some(Comment::removable("Consider **ignoring** this specific dependency. You can also accept the **NO-LICENSE** key to avoid these issues."))
         },
@@ -0,0 +1,53 @@
+use crate::license_normalize::normalize_licenses;
+use serde::Deserialize;
+
+/// Subset of an npm registry version's metadata that's relevant to license
+/// resolution.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NpmMetadata {
+    /// The modern, single-value `license` field, as a plain string. When the
+    /// version doesn't declare it as a string, the caller falls back to the
+    /// package-level `license` (a string, or the `type` of a `{ type, url }`
+    /// object).
+    pub license: Option<String>,
+    /// The legacy `licenses` array still used by a handful of older packages.
+    pub licenses: Option<Vec<LicenseObject>>,
+    /// Tarball information, used as a text-matching fallback when neither
+    /// `license` nor `licenses` is present.
+    pub dist: Option<Dist>,
+}
+
+/// The `dist` section of an npm version's metadata.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Dist {
+    /// URL of the package tarball, e.g. `https://registry.npmjs.org/foo/-/foo-1.0.0.tgz`.
+    pub tarball: String,
+}
+
+/// An entry of the legacy npm `licenses` array.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LicenseObject {
+    #[serde(rename = "type")]
+    pub license_type: Option<String>,
+}
+
+impl NpmMetadata {
+    /// Builds the normalized list of license identifiers declared by this
+    /// package version, preferring the modern `license` field over the legacy
+    /// `licenses` array. See [`normalize_licenses`] for how raw values are
+    /// canonicalized.
+    #[must_use]
+    pub fn get_licenses(&self) -> Option<Vec<String>> {
+        let raw = if let Some(license) = &self.license {
+            vec![license.clone()]
+        } else {
+            self.licenses
+                .as_ref()?
+                .iter()
+                .filter_map(|license| license.license_type.clone())
+                .collect()
+        };
+
+        Some(normalize_licenses(&raw))
+    }
+}
@@ -0,0 +1,82 @@
+use futures::{future, FutureExt};
+use licensebat_core::{
+    collector::RetrievedDependencyStreamResult, Collector, FileCollector, RetrievedDependency,
+};
+use spdx_rs::models::SPDX;
+
+pub const REUSE: &str = "reuse";
+
+/// Reads a project's own SPDX/REUSE metadata instead of querying a registry.
+///
+/// This complements the npm/yarn/rust/dart collectors, which all scrape a
+/// remote registry and then try to make sense of whatever license field it
+/// happens to expose. A project that already publishes a REUSE-compliant SPDX
+/// document has declared an authoritative `SPDX-License-Identifier` per
+/// package, so there's nothing left to retrieve or guess at: every dependency
+/// comes back already [`validated`](RetrievedDependency::validated), with no
+/// network round-trip.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Reuse;
+
+impl Reuse {
+    /// Creates a new [`Reuse`] collector.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Collector for Reuse {
+    fn get_name(&self) -> String {
+        REUSE.to_string()
+    }
+}
+
+impl FileCollector for Reuse {
+    fn get_dependency_filename(&self) -> String {
+        String::from(".spdx")
+    }
+
+    fn get_dependencies(&self, dependency_file_content: &str) -> RetrievedDependencyStreamResult {
+        let document: SPDX = spdx_rs::parsers::spdx_from_tag_value(dependency_file_content)?;
+
+        let dependencies = document
+            .package_information
+            .into_iter()
+            .map(|package| {
+                // a package isn't required to have a declared license (it
+                // may only assert what it was concluded to be, or neither);
+                // fall back to the same `NO-LICENSE` key the npm collector
+                // uses for a dependency with no license info at all.
+                let license = package
+                    .declared_license
+                    .as_ref()
+                    .or(package.concluded_license.as_ref())
+                    .map_or_else(|| "NO-LICENSE".to_owned(), ToString::to_string);
+                // every other `FileCollector` in this workspace yields
+                // `Result<RetrievedDependency, RetrievalError>` (nothing here
+                // can fail, so this is always `Ok`), since `run`'s loop
+                // destructures every item in the combined stream as a `Result`
+                future::ready(Ok(RetrievedDependency {
+                    name: package.package_name,
+                    version: package.package_version.unwrap_or_default(),
+                    url: Some(package.package_download_location),
+                    dependency_type: REUSE.to_string(),
+                    // the license was declared by the project itself, there's
+                    // nothing left to validate against a `.licrc`'s allow/deny
+                    // lists beyond what `run` already does for every collector
+                    validated: true,
+                    is_valid: true,
+                    is_ignored: false,
+                    error: None,
+                    licenses: Some(vec![license]),
+                    clarified: false,
+                    comment: None,
+                }))
+                .boxed()
+            })
+            .collect();
+
+        Ok(dependencies)
+    }
+}